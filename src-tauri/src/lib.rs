@@ -1,9 +1,673 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use argon2::Argon2;
+use minisign_verify::{PublicKey, Signature};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::Serialize;
+use tauri::path::BaseDirectory;
+use tauri::{AppHandle, Emitter, Manager, RunEvent};
+use tauri_plugin_shell::process::{CommandChild, CommandEvent};
 use tauri_plugin_shell::ShellExt;
+use tauri_plugin_updater::UpdaterExt;
+use tokio::sync::Notify;
+
+/// Backoff floor/ceiling for sidecar crash-restart.
+const RESTART_BACKOFF_MIN: Duration = Duration::from_millis(500);
+const RESTART_BACKOFF_MAX: Duration = Duration::from_secs(30);
+/// A run longer than this counts as "healthy" and resets the backoff.
+const HEALTHY_RUN_THRESHOLD: Duration = Duration::from_secs(60);
+
+/// A single line of backend output, re-emitted to the webview as `backend-log`.
+#[derive(Serialize, Clone)]
+struct BackendLogLine {
+    stream: &'static str,
+    message: String,
+    /// Present when `message` parsed as a structured JSON log record.
+    level: Option<String>,
+    fields: Option<serde_json::Value>,
+}
+
+impl BackendLogLine {
+    fn new(stream: &'static str, raw: Vec<u8>) -> Self {
+        let raw_line = String::from_utf8_lossy(&raw).trim_end().to_string();
+
+        if let Ok(mut parsed) = serde_json::from_str::<serde_json::Value>(&raw_line) {
+            if let Some(obj) = parsed.as_object_mut() {
+                let level = obj.remove("level").and_then(|v| v.as_str().map(str::to_string));
+                let message = obj
+                    .remove("message")
+                    .and_then(|v| v.as_str().map(str::to_string))
+                    .unwrap_or_else(|| raw_line.clone());
+                return Self {
+                    stream,
+                    message,
+                    level,
+                    fields: Some(serde_json::Value::Object(obj.clone())),
+                };
+            }
+        }
+
+        Self {
+            stream,
+            message: raw_line,
+            level: None,
+            fields: None,
+        }
+    }
+}
+
+/// Prefix of the handshake line the sidecar prints once it is listening.
+const LISTEN_HANDSHAKE_PREFIX: &str = "CYBERFORGE_LISTEN=";
+/// Env var consulted for the backend URL when no handshake has been seen yet
+/// (e.g. for external dev backends); a live handshake URL takes precedence.
+const BACKEND_URL_ENV_OVERRIDE: &str = "CYBERFORGE_BACKEND_URL";
+
+/// Managed handle to the running backend sidecar, if any.
+struct BackendState {
+    child: Mutex<Option<CommandChild>>,
+    /// `http://<host>:<port>` once the sidecar has handshaked its listen address.
+    url: Mutex<Option<String>>,
+    /// Secrets pulled from the vault, handed to the sidecar as env vars on (re)spawn
+    /// so they never touch disk outside the encrypted snapshot. Mutating commands
+    /// call `restart_backend` after updating this so a running sidecar picks up
+    /// the change immediately instead of on its next crash or app restart.
+    secret_envs: Mutex<HashMap<String, String>>,
+    /// Notified when the app is quitting, so the supervisor stops restarting the
+    /// sidecar instead of racing a fresh spawn against process teardown.
+    shutdown: Arc<Notify>,
+}
+
+impl BackendState {
+    fn new() -> Self {
+        Self {
+            child: Mutex::new(None),
+            url: Mutex::new(None),
+            secret_envs: Mutex::new(HashMap::new()),
+            shutdown: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Kills the running sidecar, if any, so the supervisor respawns it with the
+    /// current `secret_envs` rather than leaving a stale or now-revoked secret
+    /// loaded until the next crash or app restart.
+    fn restart_backend(&self) {
+        if let Some(child) = self.child.lock().unwrap().take() {
+            let _ = child.kill();
+        }
+    }
+}
+
+/// Parses a `CYBERFORGE_LISTEN=<addr>` handshake line into a `http://` URL.
+fn parse_listen_handshake(line: &str) -> Option<String> {
+    let addr = line.trim().strip_prefix(LISTEN_HANDSHAKE_PREFIX)?;
+    Some(format!("http://{addr}"))
+}
+
+/// Resolves the backend URL, preferring a live handshake and falling back to
+/// the env override for dev setups that run the backend outside the sidecar.
+#[tauri::command]
+async fn get_backend_url(state: tauri::State<'_, BackendState>) -> Result<String, String> {
+    if let Some(url) = state.url.lock().unwrap().clone() {
+        return Ok(url);
+    }
+    if let Ok(url) = std::env::var(BACKEND_URL_ENV_OVERRIDE) {
+        return Ok(url);
+    }
+    Err("backend URL not yet known (sidecar still starting?)".into())
+}
+
+/// Stronghold client keyspace holding CyberForge's API keys, target credentials, and
+/// scan configs. Record keys are free-form strings chosen by the caller.
+const VAULT_CLIENT_PATH: &[u8] = b"cyberforge-vault";
+/// Directory (under the platform data dir) holding the vault snapshot and its salt.
+const VAULT_DIR: &str = "cyberforge";
+const VAULT_SNAPSHOT_FILE: &str = "vault.stronghold";
+const VAULT_SALT_FILE: &str = "vault.salt";
+const VAULT_SALT_LEN: usize = 16;
+
+fn vault_dir() -> Result<std::path::PathBuf, String> {
+    let mut dir =
+        dirs::data_dir().ok_or_else(|| "could not resolve platform data directory".to_string())?;
+    dir.push(VAULT_DIR);
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir)
+}
+
+fn vault_snapshot_path() -> Result<std::path::PathBuf, String> {
+    Ok(vault_dir()?.join(VAULT_SNAPSHOT_FILE))
+}
+
+/// Loads this installation's random Argon2 salt, generating and persisting one on
+/// first use so every install derives a distinct snapshot key from the same
+/// password (a shared salt would let one precomputed attack target every install).
+fn load_or_create_vault_salt() -> Result<Vec<u8>, String> {
+    let path = vault_dir()?.join(VAULT_SALT_FILE);
+    if let Ok(existing) = std::fs::read(&path) {
+        if existing.len() == VAULT_SALT_LEN {
+            return Ok(existing);
+        }
+    }
+
+    let mut salt = vec![0u8; VAULT_SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    std::fs::write(&path, &salt).map_err(|e| e.to_string())?;
+    Ok(salt)
+}
+
+/// Derives the Stronghold snapshot key from a user password and this install's
+/// salt, shared between the plugin's own password-hash hook and our
+/// store/get/delete commands below.
+fn hash_vault_password(password: &str, salt: &[u8]) -> Vec<u8> {
+    let mut key = vec![0u8; 32];
+    Argon2::default()
+        .hash_password_into(password.as_bytes(), salt, &mut key)
+        .expect("argon2 hashing of vault password failed");
+    key
+}
+
+/// Stores a secret (API token, target credential, scan config field, ...) in the
+/// encrypted Stronghold snapshot, creating the snapshot on first use.
+#[tauri::command]
+async fn store_secret(
+    app: AppHandle,
+    password: String,
+    key: String,
+    value: String,
+) -> Result<(), String> {
+    let stronghold = app
+        .handle()
+        .state::<tauri_plugin_stronghold::stronghold::StrongholdCollection>();
+    let snapshot_path = vault_snapshot_path()?;
+    let salt = load_or_create_vault_salt()?;
+    let hashed = hash_vault_password(&password, &salt);
+    let stronghold = stronghold
+        .load_client_or_create(snapshot_path, hashed, VAULT_CLIENT_PATH)
+        .map_err(|e| e.to_string())?;
+
+    stronghold
+        .store()
+        .insert(key.clone().into_bytes(), value.clone().into_bytes(), None)
+        .map_err(|e| e.to_string())?;
+    stronghold.save().map_err(|e| e.to_string())?;
+
+    // If this key was already being forwarded to the sidecar, refresh the staged
+    // value and restart so the running backend doesn't keep using the stale one.
+    let backend = app.state::<BackendState>();
+    let mut secret_envs = backend.secret_envs.lock().unwrap();
+    if let std::collections::hash_map::Entry::Occupied(mut entry) = secret_envs.entry(key) {
+        entry.insert(value);
+        drop(secret_envs);
+        backend.restart_backend();
+    }
+
+    Ok(())
+}
+
+/// Retrieves a secret and, if `forward_to_backend` is set, stages it under its own
+/// key as an env var the supervisor will hand to the sidecar on its next (re)spawn.
+#[tauri::command]
+async fn get_secret(
+    app: AppHandle,
+    password: String,
+    key: String,
+    forward_to_backend: bool,
+) -> Result<String, String> {
+    let stronghold = app
+        .handle()
+        .state::<tauri_plugin_stronghold::stronghold::StrongholdCollection>();
+    let snapshot_path = vault_snapshot_path()?;
+    let salt = load_or_create_vault_salt()?;
+    let hashed = hash_vault_password(&password, &salt);
+    let stronghold = stronghold
+        .load_client_or_create(snapshot_path, hashed, VAULT_CLIENT_PATH)
+        .map_err(|e| e.to_string())?;
+
+    let value = stronghold
+        .store()
+        .get(key.as_bytes())
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("no secret stored for key {key:?}"))?;
+    let value = String::from_utf8(value).map_err(|e| e.to_string())?;
+
+    if forward_to_backend {
+        let backend = app.state::<BackendState>();
+        let mut secret_envs = backend.secret_envs.lock().unwrap();
+        let changed = secret_envs.get(&key) != Some(&value);
+        secret_envs.insert(key, value.clone());
+        drop(secret_envs);
+        if changed {
+            backend.restart_backend();
+        }
+    }
+
+    Ok(value)
+}
+
+/// Deletes a secret from the vault and stops forwarding it to the sidecar.
+#[tauri::command]
+async fn delete_secret(app: AppHandle, password: String, key: String) -> Result<(), String> {
+    let stronghold = app
+        .handle()
+        .state::<tauri_plugin_stronghold::stronghold::StrongholdCollection>();
+    let snapshot_path = vault_snapshot_path()?;
+    let salt = load_or_create_vault_salt()?;
+    let hashed = hash_vault_password(&password, &salt);
+    let stronghold = stronghold
+        .load_client_or_create(snapshot_path, hashed, VAULT_CLIENT_PATH)
+        .map_err(|e| e.to_string())?;
+
+    stronghold
+        .store()
+        .delete(key.as_bytes())
+        .map_err(|e| e.to_string())?;
+    stronghold.save().map_err(|e| e.to_string())?;
+
+    let backend = app.state::<BackendState>();
+    let removed = backend.secret_envs.lock().unwrap().remove(&key).is_some();
+    if removed {
+        backend.restart_backend();
+    }
+    Ok(())
+}
+
+/// Where backend sidecar release artifacts are published, overridable for self-hosted mirrors.
+const BACKEND_UPDATE_BASE_URL_ENV: &str = "CYBERFORGE_BACKEND_UPDATE_URL";
+const DEFAULT_BACKEND_UPDATE_BASE_URL: &str =
+    "https://releases.cyberforge.app/backend";
+/// minisign public key used to verify downloaded backend binaries, matching the
+/// keypair the release pipeline signs `cyberforge-backend-*` artifacts with.
+const BACKEND_BINARY_PUBKEY: &str =
+    "RWTX5Zpv8Dtxdy3xU5z8f6OMvqFvZ8nJxAi6S1mZgYkOOyT3FvJ9M4Fq";
+
+/// Argv/cwd forwarded from a second, blocked launch of the app.
+#[derive(Serialize, Clone)]
+struct SecondInstancePayload {
+    argv: Vec<String>,
+    cwd: String,
+}
+
+/// Update manifest summary returned to the frontend.
+#[derive(Serialize)]
+struct UpdateManifest {
+    version: String,
+    notes: Option<String>,
+    date: Option<String>,
+}
+
+/// Checks the configured updater endpoint for a newer release without installing it.
+#[tauri::command]
+async fn check_for_update(app: AppHandle) -> Result<Option<UpdateManifest>, String> {
+    let updater = app.updater().map_err(|e| e.to_string())?;
+    let update = updater.check().await.map_err(|e| e.to_string())?;
+    Ok(update.map(|update| UpdateManifest {
+        version: update.version.clone(),
+        notes: update.body.clone(),
+        date: update.date.map(|d| d.to_string()),
+    }))
+}
+
+/// Downloads and installs the latest app release, then swaps in a matching
+/// backend sidecar binary and lets the supervisor restart it.
+#[tauri::command]
+async fn install_update(
+    app: AppHandle,
+    state: tauri::State<'_, BackendState>,
+) -> Result<(), String> {
+    let updater = app.updater().map_err(|e| e.to_string())?;
+    let Some(update) = updater.check().await.map_err(|e| e.to_string())? else {
+        return Err("no update available".into());
+    };
+
+    update
+        .download_and_install(|_chunk, _total| {}, || {})
+        .await
+        .map_err(|e| e.to_string())?;
+    log::info!("App binary updated to {}", update.version);
+
+    // Stop the sidecar first: on Windows the old binary stays locked open for as
+    // long as the process holds it, so replacing it in place would fail.
+    state.restart_backend();
+
+    match update_backend_binary(&app).await {
+        Ok(()) => {
+            // Kill again in case the supervisor already respawned from the old
+            // binary while the download/verify above was in flight.
+            state.restart_backend();
+        }
+        Err(e) => log::warn!("App updated but backend binary update failed: {e}"),
+    }
+
+    Ok(())
+}
+
+/// Downloads the backend sidecar artifact for this platform, verifies its
+/// signature, and atomically replaces the bundled binary in place.
+async fn update_backend_binary(app: &AppHandle) -> Result<(), String> {
+    let artifact_name = format!(
+        "cyberforge-backend-{}{}",
+        current_target_triple(),
+        if cfg!(windows) { ".exe" } else { "" }
+    );
+    let base_url = std::env::var(BACKEND_UPDATE_BASE_URL_ENV)
+        .unwrap_or_else(|_| DEFAULT_BACKEND_UPDATE_BASE_URL.to_string());
+    let binary_url = format!("{base_url}/{artifact_name}");
+
+    let client = reqwest::Client::new();
+    let binary = client
+        .get(&binary_url)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?
+        .bytes()
+        .await
+        .map_err(|e| e.to_string())?;
+    let signature = client
+        .get(format!("{binary_url}.sig"))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?
+        .text()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    verify_backend_signature(&binary, &signature)?;
+
+    let bin_path = app
+        .path()
+        .resolve(format!("bin/{artifact_name}"), BaseDirectory::Resource)
+        .map_err(|e| e.to_string())?;
+    let staged_path = bin_path.with_extension("update");
+    std::fs::write(&staged_path, &binary).map_err(|e| e.to_string())?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&staged_path, std::fs::Permissions::from_mode(0o755))
+            .map_err(|e| e.to_string())?;
+    }
+
+    std::fs::rename(&staged_path, &bin_path).map_err(|e| e.to_string())?;
+    log::info!("Backend sidecar binary updated ({artifact_name})");
+    Ok(())
+}
+
+/// Verifies a downloaded backend binary against the release minisign key.
+fn verify_backend_signature(binary: &[u8], signature: &str) -> Result<(), String> {
+    let public_key = PublicKey::from_base64(BACKEND_BINARY_PUBKEY).map_err(|e| e.to_string())?;
+    let signature = Signature::decode(signature).map_err(|e| e.to_string())?;
+    public_key
+        .verify(binary, &signature, false)
+        .map_err(|_| "backend binary signature verification failed".to_string())
+}
+
+/// Maps the current build target to the triple used in sidecar artifact names.
+fn current_target_triple() -> &'static str {
+    #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
+    {
+        "aarch64-apple-darwin"
+    }
+    #[cfg(all(target_os = "macos", target_arch = "x86_64"))]
+    {
+        "x86_64-apple-darwin"
+    }
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    {
+        "x86_64-unknown-linux-gnu"
+    }
+    #[cfg(all(target_os = "windows", target_arch = "x86_64"))]
+    {
+        "x86_64-pc-windows-msvc"
+    }
+    #[cfg(not(any(
+        all(target_os = "macos", target_arch = "aarch64"),
+        all(target_os = "macos", target_arch = "x86_64"),
+        all(target_os = "linux", target_arch = "x86_64"),
+        all(target_os = "windows", target_arch = "x86_64"),
+    )))]
+    {
+        compile_error!("current_target_triple: no sidecar artifact triple is defined for this target; add one before building for it");
+    }
+}
+
+/// Spawns the backend sidecar and supervises it for the lifetime of the app,
+/// restarting it with exponential backoff if it exits unexpectedly.
+fn supervise_backend(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut backoff = RESTART_BACKOFF_MIN;
+        let shutdown = app.state::<BackendState>().shutdown.clone();
+
+        loop {
+            let shell = app.shell();
+            let sidecar = match shell.sidecar("cyberforge-backend") {
+                Ok(sidecar) => sidecar,
+                Err(e) => {
+                    log::warn!("Backend sidecar not found (dev mode?): {e}");
+                    return;
+                }
+            };
+
+            let sidecar = sidecar.envs(
+                app.state::<BackendState>()
+                    .secret_envs
+                    .lock()
+                    .unwrap()
+                    .clone(),
+            );
+
+            let (mut rx, child) = match sidecar.spawn() {
+                Ok(pair) => pair,
+                Err(e) => {
+                    log::warn!("Backend sidecar spawn failed: {e}");
+                    tokio::select! {
+                        _ = shutdown.notified() => return,
+                        _ = tokio::time::sleep(backoff) => {}
+                    }
+                    backoff = (backoff * 2).min(RESTART_BACKOFF_MAX);
+                    continue;
+                }
+            };
+            log::info!("CyberForge backend sidecar started");
+
+            let state = app.state::<BackendState>();
+            *state.child.lock().unwrap() = Some(child);
+
+            let started_at = Instant::now();
+            let mut shutting_down = false;
+            loop {
+                tokio::select! {
+                    _ = shutdown.notified() => {
+                        shutting_down = true;
+                        break;
+                    }
+                    event = rx.recv() => {
+                        let Some(event) = event else { break };
+                        match event {
+                            CommandEvent::Stdout(line) => {
+                                if let Some(addr) =
+                                    parse_listen_handshake(&String::from_utf8_lossy(&line))
+                                {
+                                    log::info!("CyberForge backend listening on {addr}");
+                                    *app.state::<BackendState>().url.lock().unwrap() = Some(addr);
+                                }
+                                let _ =
+                                    app.emit("backend-log", BackendLogLine::new("stdout", line));
+                            }
+                            CommandEvent::Stderr(line) => {
+                                let _ =
+                                    app.emit("backend-log", BackendLogLine::new("stderr", line));
+                            }
+                            CommandEvent::Terminated(payload) => {
+                                log::warn!("CyberForge backend sidecar terminated: {payload:?}");
+                                break;
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            *app.state::<BackendState>().child.lock().unwrap() = None;
+            *app.state::<BackendState>().url.lock().unwrap() = None;
+
+            if shutting_down {
+                log::info!("App is quitting, stopping backend sidecar supervisor");
+                return;
+            }
+
+            if started_at.elapsed() >= HEALTHY_RUN_THRESHOLD {
+                backoff = RESTART_BACKOFF_MIN;
+            } else {
+                backoff = (backoff * 2).min(RESTART_BACKOFF_MAX);
+            }
+
+            log::info!("Restarting backend sidecar in {backoff:?}");
+            tokio::select! {
+                _ = shutdown.notified() => return,
+                _ = tokio::time::sleep(backoff) => {}
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_well_formed_handshake_line() {
+        assert_eq!(
+            parse_listen_handshake("CYBERFORGE_LISTEN=127.0.0.1:5317"),
+            Some("http://127.0.0.1:5317".to_string())
+        );
+    }
+
+    #[test]
+    fn parses_a_handshake_line_with_trailing_whitespace() {
+        assert_eq!(
+            parse_listen_handshake("CYBERFORGE_LISTEN=127.0.0.1:5317\r\n"),
+            Some("http://127.0.0.1:5317".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_lines_without_the_handshake_prefix() {
+        assert_eq!(parse_listen_handshake("127.0.0.1:5317"), None);
+        assert_eq!(parse_listen_handshake("some unrelated log line"), None);
+        assert_eq!(parse_listen_handshake(""), None);
+    }
+
+    #[test]
+    fn backend_log_line_extracts_structured_json_fields() {
+        let line = BackendLogLine::new(
+            "stdout",
+            br#"{"level":"warn","message":"scan failed","target":"nmap"}"#.to_vec(),
+        );
+        assert_eq!(line.stream, "stdout");
+        assert_eq!(line.message, "scan failed");
+        assert_eq!(line.level.as_deref(), Some("warn"));
+        assert_eq!(
+            line.fields,
+            Some(serde_json::json!({ "target": "nmap" }))
+        );
+    }
+
+    #[test]
+    fn backend_log_line_falls_back_to_raw_text_for_plain_lines() {
+        let line = BackendLogLine::new("stderr", b"not json at all".to_vec());
+        assert_eq!(line.stream, "stderr");
+        assert_eq!(line.message, "not json at all");
+        assert_eq!(line.level, None);
+        assert_eq!(line.fields, None);
+    }
+
+    #[test]
+    fn backend_log_line_falls_back_to_raw_text_for_non_object_json() {
+        let line = BackendLogLine::new("stdout", b"[1, 2, 3]".to_vec());
+        assert_eq!(line.message, "[1, 2, 3]");
+        assert_eq!(line.level, None);
+        assert_eq!(line.fields, None);
+
+        let line = BackendLogLine::new("stdout", b"42".to_vec());
+        assert_eq!(line.message, "42");
+        assert_eq!(line.level, None);
+        assert_eq!(line.fields, None);
+    }
+
+    #[test]
+    fn rejects_garbage_signature_text() {
+        let err = verify_backend_signature(b"some binary bytes", "not a minisign signature")
+            .unwrap_err();
+        assert!(!err.is_empty());
+    }
+
+    #[test]
+    fn rejects_empty_signature() {
+        assert!(verify_backend_signature(b"some binary bytes", "").is_err());
+    }
+
+    #[test]
+    fn hash_vault_password_is_deterministic_for_the_same_password_and_salt() {
+        let salt = b"fixed-test-salt-";
+        assert_eq!(
+            hash_vault_password("hunter2", salt),
+            hash_vault_password("hunter2", salt)
+        );
+    }
+
+    #[test]
+    fn hash_vault_password_differs_across_salts() {
+        let key_a = hash_vault_password("hunter2", b"install-salt-aaaa");
+        let key_b = hash_vault_password("hunter2", b"install-salt-bbbb");
+        assert_ne!(key_a, key_b);
+    }
+
+    #[test]
+    fn hash_vault_password_differs_across_passwords() {
+        let salt = b"fixed-test-salt-";
+        let key_a = hash_vault_password("hunter2", salt);
+        let key_b = hash_vault_password("correct-horse-battery-staple", salt);
+        assert_ne!(key_a, key_b);
+    }
+
+    #[test]
+    fn hash_vault_password_produces_a_32_byte_key() {
+        assert_eq!(hash_vault_password("hunter2", b"fixed-test-salt-").len(), 32);
+    }
+}
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
+        .plugin(tauri_plugin_single_instance::init(|app, argv, cwd| {
+            log::info!("Blocked duplicate instance launch: argv={argv:?} cwd={cwd}");
+            let _ = app.emit("second-instance", SecondInstancePayload { argv, cwd });
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.unminimize();
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+        }))
         .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_updater::Builder::new().build())
+        .plugin(
+            tauri_plugin_stronghold::Builder::new(|password| {
+                let salt = load_or_create_vault_salt()
+                    .expect("failed to load or create the vault salt");
+                hash_vault_password(password, &salt)
+            })
+            .build(),
+        )
+        .manage(BackendState::new())
+        .invoke_handler(tauri::generate_handler![
+            get_backend_url,
+            check_for_update,
+            install_update,
+            store_secret,
+            get_secret,
+            delete_secret
+        ])
         .setup(|app| {
             if cfg!(debug_assertions) {
                 app.handle().plugin(
@@ -13,24 +677,21 @@ pub fn run() {
                 )?;
             }
 
-            // Spawn the Python backend sidecar (non-fatal in dev mode)
-            let shell = app.shell();
-            match shell.sidecar("cyberforge-backend") {
-                Ok(sidecar) => match sidecar.spawn() {
-                    Ok((_rx, _child)) => {
-                        log::info!("CyberForge backend sidecar started");
-                    }
-                    Err(e) => {
-                        log::warn!("Backend sidecar spawn failed (already running?): {e}");
-                    }
-                },
-                Err(e) => {
-                    log::warn!("Backend sidecar not found (dev mode?): {e}");
-                }
-            }
+            supervise_backend(app.handle().clone());
 
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let RunEvent::ExitRequested { .. } | RunEvent::Exit = event {
+                let state = app.state::<BackendState>();
+                state.shutdown.notify_waiters();
+                if let Some(child) = state.child.lock().unwrap().take() {
+                    if let Err(e) = child.kill() {
+                        log::warn!("Failed to kill backend sidecar: {e}");
+                    }
+                }
+            }
+        });
 }